@@ -3,21 +3,37 @@
 extern crate rand;
 extern crate test;
 
+use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+use std::rc::{Rc, Weak};
 use std::result::Result;
 
-#[derive(Debug)]
-pub struct BinomialHeap {
-    heads: VecDeque<Node>,
+pub struct BinomialHeap<T> {
+    heads: VecDeque<Node<T>>,
+    comparator: Box<Fn(&T, &T) -> Ordering>,
+    len: usize,
+    // The current extremum root, kept up to date by whichever operation
+    // touched it last so `peek` doesn't need to rescan `heads`. `None`
+    // exactly when the heap is empty.
+    best: RefCell<Option<Node<T>>>,
 }
 
-fn format_node_list(nodes: &VecDeque<Node>, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+impl<T: fmt::Debug> fmt::Debug for BinomialHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("BinomialHeap").field("heads", &self.heads).finish()
+    }
+}
+
+fn format_node_list<T: fmt::Display>(nodes: &VecDeque<Node<T>>, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
     try![write![f, "["]];
     let mut i = nodes.iter();
     if let Some(n) = i.next() {
-        try![write![f, "[{}", n.value]];
+        let n = n.borrow();
+        try![write![f, "[{}", n.value.as_ref().unwrap()]];
         if !n.nodes.is_empty() {
             try![write![f, " "]];
             try![format_node_list(&n.nodes, f)];
@@ -25,7 +41,8 @@ fn format_node_list(nodes: &VecDeque<Node>, f: &mut fmt::Formatter) -> Result<()
         try![write![f, "]"]];
     }
     for n in i {
-        try![write![f, ", [{}", n.value]];
+        let n = n.borrow();
+        try![write![f, ", [{}", n.value.as_ref().unwrap()]];
         if !n.nodes.is_empty() {
             try![write![f, " "]];
             try![format_node_list(&n.nodes, f)];
@@ -35,47 +52,102 @@ fn format_node_list(nodes: &VecDeque<Node>, f: &mut fmt::Formatter) -> Result<()
     write![f, "]"]
 }
 
-impl fmt::Display for BinomialHeap {
+impl<T: fmt::Display> fmt::Display for BinomialHeap<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         format_node_list(&self.heads, f)
     }
 }
 
+// `value` is an `Option` so that `take_value` can move it out of a node
+// that may still have other `Rc` clones floating around mid-traversal
+// (e.g. while `force_to_root` walks parent pointers), without requiring
+// unique ownership of the node.
 #[derive(Debug)]
-struct NodeData {
+struct NodeData<T> {
     rank: u16,
-    value: i32,
-    nodes: VecDeque<Node>,
+    value: Option<T>,
+    nodes: VecDeque<Node<T>>,
+    parent: Option<Weak<RefCell<NodeData<T>>>>,
+    handle: Option<Weak<HandleInner<T>>>,
 }
 
-type Node = Box<NodeData>;
+// Nodes are addressable (for `decrease_key`/`update`/`remove`), so unlike
+// the plain `Box<NodeData<T>>` tree this started as, they need shared,
+// interior-mutable ownership: a `Handle` and the tree itself both point
+// at the same node, and `decrease_key` mutates it through either path.
+type Node<T> = Rc<RefCell<NodeData<T>>>;
+
+// The only strong pointer from a `Handle` back into the tree. Nodes hold
+// a `Weak` reference to this (see `NodeData::handle`) so that a node and
+// its handle don't keep each other alive forever.
+struct HandleInner<T> {
+    node: RefCell<Option<Node<T>>>,
+}
+
+/// A stable reference to a value previously pushed onto a
+/// `BinomialHeap`, returned by `push`. A `Handle` survives merges and
+/// the popping of other elements, and can be used with `decrease_key`,
+/// `update`, and `remove` to reach its element directly instead of
+/// re-scanning the heap. Using a handle after its element has left the
+/// heap panics.
+pub struct Handle<T>(Rc<HandleInner<T>>);
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle(self.0.clone())
+    }
+}
+
+impl<T> Handle<T> {
+    fn current_node(&self) -> Node<T> {
+        self.0.node.borrow().clone()
+            .expect("handle used after its element was removed from the heap")
+    }
+}
 
-fn combine(mut h1: Node, h2: Node) -> Node {
-    if h1.value >= h2.value {
-        h1.rank += 1;
-        h1.nodes.push_back(h2);
+// `comparator` returns `Greater` (or `Equal`) when `a` should become the
+// parent of `b`. The default comparator is `Ord::cmp`, which yields a
+// max-heap; callers pass a reversed comparator for a min-heap.
+fn combine<T, C>(h1: Node<T>, h2: Node<T>, comparator: &C) -> Node<T>
+    where C: Fn(&T, &T) -> Ordering + ?Sized
+{
+    let h1_wins = {
+        let a = h1.borrow();
+        let b = h2.borrow();
+        comparator(a.value.as_ref().unwrap(), b.value.as_ref().unwrap()) != Ordering::Less
+    };
+    if h1_wins {
+        h2.borrow_mut().parent = Some(Rc::downgrade(&h1));
+        {
+            let mut h1_mut = h1.borrow_mut();
+            h1_mut.rank += 1;
+            h1_mut.nodes.push_back(h2);
+        }
         h1
     } else {
-        combine(h2, h1)
+        combine(h2, h1, comparator)
     }
 }
 
 // Destructively merges `a` and `b` into a new `VecDeque`.
-fn merge_nodes(a: &mut VecDeque<Node>, b: &mut VecDeque<Node>) -> VecDeque<Node> {
+fn merge_nodes<T, C>(a: &mut VecDeque<Node<T>>, b: &mut VecDeque<Node<T>>, comparator: &C) -> VecDeque<Node<T>>
+    where C: Fn(&T, &T) -> Ordering + ?Sized
+{
     let mut result = VecDeque::new();
     loop {
         match (a.pop_back(), b.pop_back()) {
             (None, None) => return result,
             (Some(h1), None) => result.push_back(h1),
             (None, Some(h2)) => result.push_back(h2),
-            (Some(h1), Some(h2)) =>
-                match h1.rank.cmp(&h2.rank) {
+            (Some(h1), Some(h2)) => {
+                let rank_order = h1.borrow().rank.cmp(&h2.borrow().rank);
+                match rank_order {
                     Ordering::Equal => {
-                        let merged = combine(h1, h2);
-                        let r = merged.rank;
-                        if r != a.back().map(|n| n.rank).unwrap_or(0) {
-                            if r != b.back().map(|n| n.rank).unwrap_or(0) {
-                                let mut recur = merge_nodes(a, b);
+                        let merged = combine(h1, h2, comparator);
+                        let r = merged.borrow().rank;
+                        if r != a.back().map(|n| n.borrow().rank).unwrap_or(0) {
+                            if r != b.back().map(|n| n.borrow().rank).unwrap_or(0) {
+                                let mut recur = merge_nodes(a, b, comparator);
                                 loop {
                                     match recur.pop_back() {
                                         None => break,
@@ -85,7 +157,7 @@ fn merge_nodes(a: &mut VecDeque<Node>, b: &mut VecDeque<Node>) -> VecDeque<Node>
                                 result.push_back(merged);
                             } else {
                                 a.push_back(merged);
-                                let mut recur = merge_nodes(a, b);
+                                let mut recur = merge_nodes(a, b, comparator);
                                 loop {
                                     match recur.pop_back() {
                                         None => break,
@@ -94,9 +166,9 @@ fn merge_nodes(a: &mut VecDeque<Node>, b: &mut VecDeque<Node>) -> VecDeque<Node>
                                 }
                             }
                         } else {
-                            if r != b.back().map(|n| n.rank).unwrap_or(0) {
+                            if r != b.back().map(|n| n.borrow().rank).unwrap_or(0) {
                                 b.push_back(merged);
-                                let mut recur = merge_nodes(a, b);
+                                let mut recur = merge_nodes(a, b, comparator);
                                 loop {
                                     match recur.pop_back() {
                                         None => break,
@@ -104,7 +176,7 @@ fn merge_nodes(a: &mut VecDeque<Node>, b: &mut VecDeque<Node>) -> VecDeque<Node>
                                     }
                                 }
                             } else {
-                                let mut recur = merge_nodes(a, b);
+                                let mut recur = merge_nodes(a, b, comparator);
                                 loop {
                                     match recur.pop_back() {
                                         None => break,
@@ -117,7 +189,7 @@ fn merge_nodes(a: &mut VecDeque<Node>, b: &mut VecDeque<Node>) -> VecDeque<Node>
                     },
                     Ordering::Less => {
                         b.push_back(h2);
-                        let mut recur = merge_nodes(a, b);
+                        let mut recur = merge_nodes(a, b, comparator);
                         loop {
                             match recur.pop_back() {
                                 None => break,
@@ -128,7 +200,7 @@ fn merge_nodes(a: &mut VecDeque<Node>, b: &mut VecDeque<Node>) -> VecDeque<Node>
                     },
                     Ordering::Greater => {
                         a.push_back(h1);
-                        let mut recur = merge_nodes(b, a);
+                        let mut recur = merge_nodes(b, a, comparator);
                         loop {
                             match recur.pop_back() {
                                 None => break,
@@ -137,69 +209,539 @@ fn merge_nodes(a: &mut VecDeque<Node>, b: &mut VecDeque<Node>) -> VecDeque<Node>
                         }
                         result.push_back(h2);
                     },
-                },
+                }
+            },
+        }
+    }
+}
+
+// Swaps the stored value and handle back-pointer between two nodes in
+// place (rather than moving the `Rc` nodes themselves), then re-points
+// each handle's forward reference at its new slot. This is how
+// decrease-key/sift-up/sift-down/remove are implemented without
+// invalidating `Handle`s held by callers: the tree's `Rc` identities
+// never move, only the payload each one is currently carrying does.
+fn swap_slots<T>(a: &Node<T>, b: &Node<T>) {
+    {
+        let mut a_mut = a.borrow_mut();
+        let mut b_mut = b.borrow_mut();
+        mem::swap(&mut a_mut.value, &mut b_mut.value);
+        mem::swap(&mut a_mut.handle, &mut b_mut.handle);
+    }
+    if let Some(h) = a.borrow().handle.as_ref().and_then(|w| w.upgrade()) {
+        *h.node.borrow_mut() = Some(a.clone());
+    }
+    if let Some(h) = b.borrow().handle.as_ref().and_then(|w| w.upgrade()) {
+        *h.node.borrow_mut() = Some(b.clone());
+    }
+}
+
+// Extracts the value out of a node being dropped from the tree,
+// severing its handle's forward pointer (if the handle is still alive)
+// so that later use of the now-stale `Handle` panics instead of
+// aliasing a slot that no longer belongs to the heap.
+fn take_value<T>(node: Node<T>) -> T {
+    if let Some(h) = node.borrow().handle.as_ref().and_then(|w| w.upgrade()) {
+        *h.node.borrow_mut() = None;
+    }
+    node.borrow_mut().value.take().expect("node value taken twice")
+}
+
+// Sifts `node` up toward its tree root, swapping slots with its parent
+// for as long as `node` outranks it. O(log n) in the size of the heap,
+// since a node's depth is bounded by its tree's rank.
+// Returns the node it finally comes to rest at (the new root, if it
+// rose all the way), so callers can cheaply check whether it's now a
+// candidate for the cached extremum.
+fn sift_up<T, C>(mut node: Node<T>, comparator: &C) -> Node<T>
+    where C: Fn(&T, &T) -> Ordering + ?Sized
+{
+    loop {
+        let parent = node.borrow().parent.as_ref().and_then(|w| w.upgrade());
+        let parent = match parent {
+            Some(p) => p,
+            None => return node,
+        };
+        let should_swap = {
+            let n = node.borrow();
+            let p = parent.borrow();
+            comparator(n.value.as_ref().unwrap(), p.value.as_ref().unwrap()) == Ordering::Greater
+        };
+        if !should_swap {
+            return node;
+        }
+        swap_slots(&parent, &node);
+        node = parent;
+    }
+}
+
+// Sifts `node` down toward its leaves, swapping slots with whichever
+// child currently outranks it, until order is restored.
+fn sift_down<T, C>(mut node: Node<T>, comparator: &C)
+    where C: Fn(&T, &T) -> Ordering + ?Sized
+{
+    loop {
+        let best_child = {
+            let n = node.borrow();
+            let mut best: Option<Node<T>> = None;
+            for child in n.nodes.iter() {
+                let take_child = match best {
+                    None => true,
+                    Some(ref b) => {
+                        let bb = b.borrow();
+                        let cc = child.borrow();
+                        comparator(cc.value.as_ref().unwrap(), bb.value.as_ref().unwrap()) == Ordering::Greater
+                    },
+                };
+                if take_child {
+                    best = Some(child.clone());
+                }
+            }
+            best
+        };
+        let best_child = match best_child {
+            Some(c) => c,
+            None => return,
+        };
+        let should_swap = {
+            let n = node.borrow();
+            let c = best_child.borrow();
+            comparator(c.value.as_ref().unwrap(), n.value.as_ref().unwrap()) == Ordering::Greater
+        };
+        if !should_swap {
+            return;
+        }
+        swap_slots(&node, &best_child);
+        node = best_child;
+    }
+}
+
+// Unconditionally swaps `node` up to its tree's root, ignoring heap
+// order, and returns the (now-relocated) root. Used by `remove`: once
+// the value we want out is sitting at the root, it can be popped like
+// any other root. Heap order among the nodes left behind is preserved
+// because the original parent/child relationships were heap-ordered,
+// and that ordering is transitive along the rotated path.
+fn force_to_root<T>(mut node: Node<T>) -> Node<T> {
+    loop {
+        let parent = node.borrow().parent.as_ref().and_then(|w| w.upgrade());
+        match parent {
+            Some(p) => {
+                swap_slots(&p, &node);
+                node = p;
+            },
+            None => return node,
+        }
+    }
+}
+
+// Scans every root to find the current extremum. O(number of roots);
+// used to rebuild the cached pointer after an operation (`pop`,
+// `remove`, a worsening `update`) that can move the extremum anywhere.
+fn scan_best_root<T, C>(heads: &VecDeque<Node<T>>, comparator: &C) -> Option<Node<T>>
+    where C: Fn(&T, &T) -> Ordering + ?Sized
+{
+    let mut best: Option<Node<T>> = None;
+    for node in heads.iter() {
+        let take = match best {
+            None => true,
+            Some(ref b) => {
+                let bb = b.borrow();
+                let nn = node.borrow();
+                comparator(nn.value.as_ref().unwrap(), bb.value.as_ref().unwrap()) == Ordering::Greater
+            },
+        };
+        if take {
+            best = Some(node.clone());
         }
     }
+    best
 }
 
-impl BinomialHeap {
+impl<T: Ord + 'static> BinomialHeap<T> {
+    /// Creates an empty max-heap ordered by `T`'s natural `Ord`
+    /// implementation. Use `with_comparator` for a min-heap or any other
+    /// ordering.
     pub fn new() -> Self {
-        BinomialHeap { heads: VecDeque::new() }
+        BinomialHeap::with_comparator(T::cmp as fn(&T, &T) -> Ordering)
     }
 
-    pub fn push(&mut self, value: i32) {
-        let mut v = VecDeque::new();
-        v.push_back(Box::new(NodeData {
-            rank: 0,
-            value: value,
-            nodes: VecDeque::new()
-        }));
-        self.heads = merge_nodes(
-            &mut self.heads,
-            &mut v);
+    /// Builds a heap from `values` in O(n), ordered by `T`'s natural
+    /// `Ord` implementation. Wraps each value in a rank-0 tree and
+    /// ripples the rank-0..rank-k carries through once, the way
+    /// incrementing a binary counter by one bit at a time amortizes to
+    /// O(1) per element, rather than re-merging the whole root list on
+    /// every insert as repeated `push` does.
+    pub fn from_vec(values: Vec<T>) -> Self {
+        let comparator = T::cmp as fn(&T, &T) -> Ordering;
+        let len = values.len();
+        let mut carries: Vec<Option<Node<T>>> = Vec::new();
+        for value in values {
+            let mut carry: Node<T> = Rc::new(RefCell::new(NodeData {
+                rank: 0,
+                value: Some(value),
+                nodes: VecDeque::new(),
+                parent: None,
+                handle: None,
+            }));
+            let mut rank = 0usize;
+            loop {
+                if rank == carries.len() {
+                    carries.push(Some(carry));
+                    break;
+                }
+                match carries[rank].take() {
+                    None => {
+                        carries[rank] = Some(carry);
+                        break;
+                    },
+                    Some(existing) => {
+                        carry = combine(existing, carry, &comparator);
+                        rank += 1;
+                    },
+                }
+            }
+        }
+        let heads: VecDeque<Node<T>> = carries.into_iter().filter_map(|c| c).collect();
+        let best = scan_best_root(&heads, &comparator);
+        BinomialHeap {
+            heads: heads,
+            comparator: Box::new(comparator),
+            len: len,
+            best: RefCell::new(best),
+        }
     }
+}
 
-    pub fn pop(&mut self) -> Option<i32> {
-        if self.heads.is_empty() {
-            return None
+impl<T> BinomialHeap<T> {
+    /// Creates an empty heap ordered by `comparator`, where `comparator(a,
+    /// b) == Greater` means `a` has higher priority than `b`.
+    pub fn with_comparator<C>(comparator: C) -> Self
+        where C: Fn(&T, &T) -> Ordering + 'static
+    {
+        BinomialHeap {
+            heads: VecDeque::new(),
+            comparator: Box::new(comparator),
+            len: 0,
+            best: RefCell::new(None),
         }
-        let mut min_idx = 0usize;
-        for (i, node) in self.heads.iter().enumerate() {
-            if node.value > self.heads[min_idx].value {
-                min_idx = i;
+    }
+
+    // Updates the cached extremum if the root of the tree `node` now sits
+    // in is an improvement over (or the heap's first) cached candidate.
+    // Callers that can only ever introduce a single new candidate root --
+    // `push`, `merge`, and the sift-up path of `decrease_key`/`update` --
+    // use this instead of rescanning every root.
+    fn note_root_candidate(&self, node: &Node<T>) {
+        // Combining equal-ranked trees can bury a root under an equal or
+        // better winner, so both the cached node and `node` are first
+        // replaced by the root of the tree they now sit in.
+        fn tree_root<T>(mut n: Node<T>) -> Node<T> {
+            loop {
+                let parent = n.borrow().parent.as_ref().and_then(|w| w.upgrade());
+                match parent {
+                    Some(p) => n = p,
+                    None => return n,
+                }
             }
         }
-        let NodeData { value, mut nodes, .. } =
-            *self.heads.remove(min_idx).unwrap();
+        let cached = self.best.borrow().clone().map(tree_root);
+        *self.best.borrow_mut() = cached;
+        let node = tree_root(node.clone());
+        let should_replace = match *self.best.borrow() {
+            None => true,
+            Some(ref b) => {
+                let bb = b.borrow();
+                let nn = node.borrow();
+                (self.comparator)(nn.value.as_ref().unwrap(), bb.value.as_ref().unwrap()) == Ordering::Greater
+            },
+        };
+        if should_replace {
+            *self.best.borrow_mut() = Some(node);
+        }
+    }
+
+    /// Inserts `value` and returns a `Handle` that can later be passed to
+    /// `decrease_key`, `update`, or `remove` to reach it directly.
+    pub fn push(&mut self, value: T) -> Handle<T> {
+        let node: Node<T> = Rc::new(RefCell::new(NodeData {
+            rank: 0,
+            value: Some(value),
+            nodes: VecDeque::new(),
+            parent: None,
+            handle: None,
+        }));
+        let handle_inner = Rc::new(HandleInner { node: RefCell::new(Some(node.clone())) });
+        node.borrow_mut().handle = Some(Rc::downgrade(&handle_inner));
+
+        let mut v = VecDeque::new();
+        v.push_back(node.clone());
+        self.heads = merge_nodes(&mut self.heads, &mut v, &*self.comparator);
+        self.len += 1;
+        self.note_root_candidate(&node);
+
+        Handle(handle_inner)
+    }
 
-        self.heads = merge_nodes(
-            &mut self.heads,
-            &mut nodes);
+    // Removes the root at `idx`, re-merges its children back into
+    // `heads`, and returns the extracted value. Shared by `pop` (which
+    // picks `idx` via the cached extremum) and `remove` (which has
+    // already rotated the target to some root via `force_to_root`). The
+    // extremum could end up anywhere among the remaining roots, so this
+    // pays for a full rescan.
+    fn extract_root(&mut self, idx: usize) -> T {
+        let node = self.heads.remove(idx).unwrap();
+        let mut children = {
+            let mut n = node.borrow_mut();
+            mem::replace(&mut n.nodes, VecDeque::new())
+        };
+        for child in children.iter() {
+            child.borrow_mut().parent = None;
+        }
+        self.heads = merge_nodes(&mut self.heads, &mut children, &*self.comparator);
+        self.len -= 1;
+        *self.best.borrow_mut() = scan_best_root(&self.heads, &*self.comparator);
+        take_value(node)
+    }
 
-        return Some(value)
+    pub fn pop(&mut self) -> Option<T> {
+        let best = match self.best.borrow().clone() {
+            None => return None,
+            Some(b) => b,
+        };
+        let idx = self.heads.iter().position(|h| Rc::ptr_eq(h, &best))
+            .expect("cached extremum missing from root list");
+        Some(self.extract_root(idx))
     }
 
-    pub fn peek(&self) -> Option<i32> {
-        self.heads.iter().map(|n| n.value).max()
+    /// Returns a clone of the current extremum, or `None` if the heap is
+    /// empty. Clones rather than borrowing because the cached pointer and
+    /// the element itself sit behind two separate `RefCell`s, so handing
+    /// back a reference into the second without re-borrowing the first
+    /// isn't possible.
+    pub fn peek(&self) -> Option<T> where T: Clone {
+        self.best.borrow().as_ref().map(|node| node.borrow().value.clone().unwrap())
     }
 
     pub fn is_empty(&self) -> bool {
-        self.heads.is_empty()
+        self.len == 0
     }
 
     pub fn len(&self) -> usize {
-        let mut sz = 0;
-        for node in self.heads.iter() {
-            match node.rank {
-                0 => sz += 1,
-                x => sz += 2 << (x - 1),
-            }
+        self.len
+    }
+
+    pub fn merge(&mut self, mut other: BinomialHeap<T>) {
+        self.heads = merge_nodes(&mut self.heads, &mut other.heads, &*self.comparator);
+        self.len += other.len;
+        if let Some(candidate) = other.best.into_inner() {
+            self.note_root_candidate(&candidate);
+        }
+    }
+
+    /// Consumes the heap and returns its elements ordered from lowest
+    /// priority to highest under the heap's comparator -- the reverse of
+    /// repeated `pop` -- mirroring
+    /// `std::collections::BinaryHeap::into_sorted_vec`.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut v = Vec::with_capacity(self.len());
+        while let Some(value) = self.pop() {
+            v.push(value);
+        }
+        v.reverse();
+        v
+    }
+
+    /// Yields a clone of every element in arbitrary order. Because elements live
+    /// behind the `RefCell`s that make `decrease_key`/`update` possible,
+    /// this clones each value out rather than handing back a `&T`.
+    pub fn iter(&self) -> Iter<T> where T: Clone {
+        Iter {
+            stack: self.heads.iter().cloned().collect(),
+            remaining: self.len(),
+        }
+    }
+
+    /// Removes and yields every element in arbitrary order, leaving the
+    /// heap empty.
+    pub fn drain(&mut self) -> Drain<T> {
+        let remaining = self.len;
+        self.len = 0;
+        *self.best.borrow_mut() = None;
+        let heads = mem::replace(&mut self.heads, VecDeque::new());
+        Drain {
+            stack: heads.into_iter().collect(),
+            remaining: remaining,
+        }
+    }
+
+    /// Improves `handle`'s value under the heap's comparator (the
+    /// classic "decrease-key" operation, named for min-heaps) and
+    /// restores heap order by sifting the element up toward the root.
+    /// `new` must not be worse than the current value; use `update` if
+    /// that isn't guaranteed.
+    pub fn decrease_key(&mut self, handle: &Handle<T>, new: T) {
+        let node = handle.current_node();
+        node.borrow_mut().value = Some(new);
+        let root = sift_up(node, &*self.comparator);
+        self.note_root_candidate(&root);
+    }
+
+    /// Changes `handle`'s value to `new` and restores heap order,
+    /// sifting the element up or down as needed. Prefer `decrease_key`
+    /// when the caller already knows `new` is an improvement, since it
+    /// skips the comparison against the old value.
+    pub fn update(&mut self, handle: &Handle<T>, new: T) {
+        let node = handle.current_node();
+        let improved = {
+            let mut n = node.borrow_mut();
+            let old = n.value.take().unwrap();
+            let improved = (self.comparator)(&new, &old) == Ordering::Greater;
+            n.value = Some(new);
+            improved
+        };
+        if improved {
+            let root = sift_up(node, &*self.comparator);
+            self.note_root_candidate(&root);
+        } else {
+            sift_down(node, &*self.comparator);
+            *self.best.borrow_mut() = scan_best_root(&self.heads, &*self.comparator);
+        }
+    }
+
+    /// Removes the element referenced by `handle`, wherever it sits in
+    /// the forest, and returns its value. Implemented as a decrease-key
+    /// to the root followed by extracting that root.
+    pub fn remove(&mut self, handle: &Handle<T>) -> Option<T> {
+        let node = handle.current_node();
+        let root = force_to_root(node);
+        let idx = match self.heads.iter().position(|h| Rc::ptr_eq(h, &root)) {
+            Some(idx) => idx,
+            None => return None,
+        };
+        Some(self.extract_root(idx))
+    }
+}
+
+/// An iterator over clones of the elements of a `BinomialHeap`, in
+/// arbitrary order. Created by `BinomialHeap::iter`.
+pub struct Iter<T> {
+    stack: Vec<Node<T>>,
+    remaining: usize,
+}
+
+impl<T: Clone> Iterator for Iter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = match self.stack.pop() {
+            None => return None,
+            Some(node) => node,
+        };
+        self.remaining -= 1;
+        let n = node.borrow();
+        for child in n.nodes.iter() {
+            self.stack.push(child.clone());
+        }
+        Some(n.value.clone().unwrap())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a BinomialHeap<T> {
+    type Item = T;
+    type IntoIter = Iter<T>;
+
+    fn into_iter(self) -> Iter<T> {
+        self.iter()
+    }
+}
+
+/// A consuming iterator over the elements of a `BinomialHeap`, in arbitrary
+/// order. Created by `BinomialHeap::into_iter`.
+pub struct IntoIter<T> {
+    stack: Vec<Node<T>>,
+    remaining: usize,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = match self.stack.pop() {
+            None => return None,
+            Some(node) => node,
+        };
+        self.remaining -= 1;
+        let children = {
+            let mut n = node.borrow_mut();
+            mem::replace(&mut n.nodes, VecDeque::new())
+        };
+        self.stack.extend(children);
+        Some(take_value(node))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> IntoIterator for BinomialHeap<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        let remaining = self.len();
+        IntoIter {
+            stack: self.heads.into_iter().collect(),
+            remaining: remaining,
         }
-        return sz
     }
+}
+
+/// A draining iterator over the elements of a `BinomialHeap`, in arbitrary
+/// order. Created by `BinomialHeap::drain`.
+pub struct Drain<T> {
+    stack: Vec<Node<T>>,
+    remaining: usize,
+}
+
+impl<T> Iterator for Drain<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = match self.stack.pop() {
+            None => return None,
+            Some(node) => node,
+        };
+        self.remaining -= 1;
+        let children = {
+            let mut n = node.borrow_mut();
+            mem::replace(&mut n.nodes, VecDeque::new())
+        };
+        self.stack.extend(children);
+        Some(take_value(node))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+// Detaches the elements the caller did not iterate over, so their
+// handles go stale and panic on use just like those of yielded ones.
+impl<T> Drop for Drain<T> {
+    fn drop(&mut self) {
+        while let Some(_) = self.next() {}
+    }
+}
 
-    pub fn merge(&mut self, mut other: BinomialHeap) {
-        self.heads = merge_nodes(&mut self.heads, &mut other.heads);
+impl<T: Ord + 'static> FromIterator<T> for BinomialHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        BinomialHeap::from_vec(iter.into_iter().collect())
     }
 }
 
@@ -213,7 +755,7 @@ mod mytest {
 
     #[test]
     fn instantiate_empty_heap() {
-        BinomialHeap::new();
+        BinomialHeap::<i32>::new();
     }
 
     #[test]
@@ -228,6 +770,116 @@ mod mytest {
         assert_eq![t.pop(), Some(23i32)];
     }
 
+    #[test]
+    fn min_heap_via_comparator() {
+        let mut t = BinomialHeap::with_comparator(|a: &i32, b: &i32| b.cmp(a));
+        for v in [5, 3, 8, 1, 9].iter() {
+            t.push(*v);
+        }
+        let mut popped = Vec::new();
+        while let Some(v) = t.pop() {
+            popped.push(v);
+        }
+        assert_eq![popped, vec![1, 3, 5, 8, 9]];
+    }
+
+    #[test]
+    fn iteration() {
+        let mut t = BinomialHeap::new();
+        for v in [5, 3, 8, 1, 9].iter() {
+            t.push(*v);
+        }
+
+        let mut seen: Vec<i32> = t.iter().collect();
+        seen.sort();
+        assert_eq![seen, vec![1, 3, 5, 8, 9]];
+
+        let mut drained: Vec<i32> = t.drain().collect();
+        drained.sort();
+        assert_eq![drained, vec![1, 3, 5, 8, 9]];
+        assert![t.is_empty()];
+
+        let t: BinomialHeap<i32> = vec![5, 3, 8, 1, 9].into_iter().collect();
+        let mut owned: Vec<i32> = t.into_iter().collect();
+        owned.sort();
+        assert_eq![owned, vec![1, 3, 5, 8, 9]];
+    }
+
+    #[test]
+    fn decrease_key_reorders() {
+        let mut t = BinomialHeap::new();
+        t.push(1);
+        let h5 = t.push(5);
+        t.push(3);
+        t.decrease_key(&h5, 9);
+        assert_eq![t.pop(), Some(9)];
+    }
+
+    #[test]
+    fn update_moves_value_down() {
+        let mut t = BinomialHeap::new();
+        let h9 = t.push(9);
+        t.push(5);
+        t.push(3);
+        t.update(&h9, 1);
+        assert_eq![t.pop(), Some(5)];
+    }
+
+    #[test]
+    fn remove_arbitrary_element() {
+        let mut t = BinomialHeap::new();
+        t.push(1);
+        let h5 = t.push(5);
+        t.push(3);
+        assert_eq![t.remove(&h5), Some(5)];
+        assert_eq![t.len(), 2];
+        let mut rest: Vec<i32> = t.into_iter().collect();
+        rest.sort();
+        assert_eq![rest, vec![1, 3]];
+    }
+
+    #[test]
+    fn peek_tracks_extremum_across_operations() {
+        let mut t = BinomialHeap::new();
+        assert_eq![t.peek(), None];
+        t.push(3);
+        let h5 = t.push(5);
+        t.push(1);
+        assert_eq![t.peek(), Some(5)];
+
+        let mut other = BinomialHeap::new();
+        other.push(9);
+        t.merge(other);
+        assert_eq![t.peek(), Some(9)];
+
+        t.update(&h5, 20);
+        assert_eq![t.peek(), Some(20)];
+
+        t.pop();
+        assert_eq![t.peek(), Some(9)];
+    }
+
+    #[test]
+    fn from_vec_builds_same_heap_as_repeated_push() {
+        let values = vec![5, 3, 8, 1, 9, 2, 7];
+        let t = BinomialHeap::from_vec(values.clone());
+        let mut pushed = BinomialHeap::new();
+        for v in values.iter() {
+            pushed.push(*v);
+        }
+        assert_eq![t.len(), values.len()];
+        assert_eq![t.len(), pushed.len()];
+        let sorted = t.into_sorted_vec();
+        assert_eq![sorted, vec![1, 2, 3, 5, 7, 8, 9]];
+        assert_eq![sorted, pushed.into_sorted_vec()];
+    }
+
+    #[test]
+    fn into_sorted_vec_sorts_ascending() {
+        let t: BinomialHeap<i32> = vec![5, 3, 8, 1, 9].into_iter().collect();
+        assert_eq![t.into_sorted_vec(), vec![1, 3, 5, 8, 9]];
+    }
+
     fn get_values() -> Vec<i32> {
         let seed: &[_] = &[1, 2, 3, 4];
         let mut rng: StdRng = SeedableRng::from_seed(seed);
@@ -260,6 +912,109 @@ mod mytest {
         });
     }
 
+    #[test]
+    fn merge_exercises_every_rank_combination() {
+        // Merges a single tree of every rank 0..=10 (2^i elements) with a
+        // single tree of every rank 0..=10, so every equal-rank combine of
+        // two trees in `merge_nodes` gets hit; root lists of several trees,
+        // and the longer carry chains they cause, are not covered here.
+        for i in 0..11 {
+            for j in 0..11 {
+                let a_len = 1 << i;
+                let b_len = 1 << j;
+                let a: Vec<i32> = (0..a_len).collect();
+                let b: Vec<i32> = (a_len..a_len + b_len).collect();
+                let mut expected: Vec<i32> = a.iter().cloned().chain(b.iter().cloned()).collect();
+                expected.sort();
+
+                let mut heap_a = BinomialHeap::from_vec(a);
+                let heap_b = BinomialHeap::from_vec(b);
+                heap_a.merge(heap_b);
+                assert_eq![heap_a.len(), expected.len()];
+                assert_eq![heap_a.into_sorted_vec(), expected];
+            }
+        }
+    }
+
+    #[test]
+    fn differential_against_vec_and_binary_heap() {
+        let seed: &[_] = &[7, 14, 21, 28];
+        let mut rng: StdRng = SeedableRng::from_seed(seed);
+
+        let mut heap = BinomialHeap::new();
+        let mut model: Vec<i32> = Vec::new();
+        let mut oracle: BinaryHeap<i32> = BinaryHeap::new();
+        let mut live: Vec<(::Handle<i32>, i32)> = Vec::new();
+
+        for _ in 0..5000 {
+            assert_eq![heap.len(), model.len()];
+            assert_eq![heap.is_empty(), model.is_empty()];
+            let expected_max = model.iter().cloned().max();
+            assert_eq![heap.peek(), expected_max];
+
+            match rng.gen_range(0, 6) {
+                0 => {
+                    let v = rng.gen_range(-1000, 1000);
+                    let h = heap.push(v);
+                    model.push(v);
+                    oracle.push(v);
+                    live.push((h, v));
+                },
+                1 => {
+                    let popped = heap.pop();
+                    assert_eq![popped, oracle.pop()];
+                    if let Some(v) = popped {
+                        let idx = model.iter().position(|x| *x == v).unwrap();
+                        model.remove(idx);
+                        // With duplicate values the popped element need not
+                        // be the first live entry holding `v`, so drop
+                        // whichever handle the heap actually detached.
+                        live.retain(|&(ref h, _)| h.0.node.borrow().is_some());
+                    }
+                },
+                2 if !live.is_empty() => {
+                    let idx = rng.gen_range(0, live.len());
+                    let (old_value, bump) = {
+                        let &(_, old_value) = &live[idx];
+                        (old_value, rng.gen_range(0, 1000))
+                    };
+                    let new_value = old_value + bump;
+                    heap.decrease_key(&live[idx].0, new_value);
+                    live[idx].1 = new_value;
+                    let model_idx = model.iter().position(|x| *x == old_value).unwrap();
+                    model[model_idx] = new_value;
+                    // `BinaryHeap` has no way to rekey a single element in
+                    // place, so rebuild the oracle from the model instead.
+                    oracle = model.iter().cloned().collect();
+                },
+                3 if !live.is_empty() => {
+                    let idx = rng.gen_range(0, live.len());
+                    let (handle, value) = live.remove(idx);
+                    assert_eq![heap.remove(&handle), Some(value)];
+                    let model_idx = model.iter().position(|x| *x == value).unwrap();
+                    model.remove(model_idx);
+                    oracle = model.iter().cloned().collect();
+                },
+                4 => {
+                    let mut other = BinomialHeap::new();
+                    for _ in 0..rng.gen_range(0, 10) {
+                        let v = rng.gen_range(-1000, 1000);
+                        let h = other.push(v);
+                        model.push(v);
+                        oracle.push(v);
+                        live.push((h, v));
+                    }
+                    heap.merge(other);
+                },
+                _ => {},
+            }
+        }
+
+        let mut expected: Vec<i32> = model.clone();
+        expected.sort();
+        assert_eq![heap.into_sorted_vec(), expected];
+    }
+
     #[bench]
     fn bench_builtin(b: &mut Bencher) {
         let values = get_values();